@@ -75,12 +75,27 @@ fn encode_commands(args: &Vec<Arg>, cursor: u64) -> Vec<u8> {
     cmd
 }
 
-// fn encode_pipeline(cmd: &[Cmd], atomic: bool) -> Vec<u8> {
-//     let mut rv = vec![];
-//     if atomic {
-//         rc.extend(c)
-//     }
-// }
+fn encode_pipeline(cmds: &[Cmd], atomic: bool) -> Vec<u8> {
+    let mut rv = vec![];
+    if atomic {
+        let mut multi = Cmd::new();
+        multi.arg("MULTI");
+        rv.extend(multi.get_packed_command());
+
+        for cmd in cmds {
+            rv.extend(cmd.get_packed_command());
+        }
+
+        let mut exec = Cmd::new();
+        exec.arg("EXEC");
+        rv.extend(exec.get_packed_command());
+    } else {
+        for cmd in cmds {
+            rv.extend(cmd.get_packed_command());
+        }
+    }
+    rv
+}
 
 impl Cmd {
     pub fn new() -> Cmd {
@@ -122,7 +137,57 @@ impl Cmd {
     #[inline]
     pub fn in_scan_mode(&self) -> bool {
         self.cursor.is_some()
-    }    
+    }
+
+    /// Marks this command as ignored so that a future response-matching
+    /// layer can discard its reply (e.g. the `+QUEUED` reply a command
+    /// gets inside a `MULTI`/`EXEC` transaction).
+    #[inline]
+    pub fn ignore(&mut self) -> &mut Cmd {
+        self.is_ignored = true;
+        self
+    }
+
+    #[inline]
+    pub fn is_ignored(&self) -> bool {
+        self.is_ignored
+    }
+}
+
+impl Pipeline {
+    pub fn new() -> Pipeline {
+        Pipeline {
+            commands: vec![],
+            transaction_mode: false,
+        }
+    }
+
+    /// Adds a new command to the pipeline and returns a mutable reference
+    /// to it so that arguments can be chained onto it.
+    pub fn cmd(&mut self, name: &str) -> &mut Cmd {
+        let mut cmd = Cmd::new();
+        cmd.arg(name);
+        self.add_command(cmd)
+    }
+
+    /// Adds an already built command to the pipeline, returning a mutable
+    /// reference to it so it can be tweaked further (e.g. `.ignore()`).
+    pub fn add_command(&mut self, cmd: Cmd) -> &mut Cmd {
+        self.commands.push(cmd);
+        self.commands.last_mut().unwrap()
+    }
+
+    /// Switches the pipeline into transaction mode, wrapping the commands
+    /// in a `MULTI`/`EXEC` block once packed.
+    pub fn atomic(&mut self) -> &mut Pipeline {
+        self.transaction_mode = true;
+        self
+    }
+
+    #[inline]
+    pub fn get_packed_pipeline(&self) -> Vec<u8> {
+        encode_pipeline(&self.commands, self.transaction_mode)
+    }
 }
 
 #[cfg(test)]
@@ -139,4 +204,41 @@ mod test {
                               $2\r\n42\r\n".as_bytes();
         assert_eq!(cmd.get_packed_command(), serialized_cmd);
     }
+
+    #[test]
+    fn test_pipeline_ser() {
+        let mut pipeline = Pipeline::new();
+        pipeline.cmd("SET").arg("my_key").arg(42);
+        pipeline.cmd("GET").arg("my_key");
+
+        let serialized_pipeline = "*3\r\n\
+                                   $3\r\nSET\r\n\
+                                   $6\r\nmy_key\r\n\
+                                   $2\r\n42\r\n\
+                                   *2\r\n\
+                                   $3\r\nGET\r\n\
+                                   $6\r\nmy_key\r\n".as_bytes();
+        assert_eq!(pipeline.get_packed_pipeline(), serialized_pipeline);
+    }
+
+    #[test]
+    fn test_atomic_pipeline_ser() {
+        let mut pipeline = Pipeline::new();
+        pipeline.atomic();
+        pipeline.cmd("SET").arg("my_key").arg(42);
+        pipeline.cmd("GET").arg("my_key");
+
+        let serialized_pipeline = "*1\r\n\
+                                   $5\r\nMULTI\r\n\
+                                   *3\r\n\
+                                   $3\r\nSET\r\n\
+                                   $6\r\nmy_key\r\n\
+                                   $2\r\n42\r\n\
+                                   *2\r\n\
+                                   $3\r\nGET\r\n\
+                                   $6\r\nmy_key\r\n\
+                                   *1\r\n\
+                                   $4\r\nEXEC\r\n".as_bytes();
+        assert_eq!(pipeline.get_packed_pipeline(), serialized_pipeline);
+    }
 }
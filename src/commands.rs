@@ -0,0 +1,83 @@
+use cmd::Cmd;
+use types::{Expiry, ToRedisArgs};
+
+/// Builds `SET key value <expiry>`, e.g. `SET key value EX 30`.
+pub fn set_ex<K: ToRedisArgs, V: ToRedisArgs>(key: K, value: V, expiry: Expiry) -> Cmd {
+    let mut cmd = Cmd::new();
+    cmd.arg("SET").arg(key).arg(value).arg(expiry);
+    cmd
+}
+
+/// Builds `GETEX key <expiry>`, e.g. `GETEX key EX 30`.
+pub fn getex<K: ToRedisArgs>(key: K, expiry: Expiry) -> Cmd {
+    let mut cmd = Cmd::new();
+    cmd.arg("GETEX").arg(key).arg(expiry);
+    cmd
+}
+
+/// Builds `EXPIRE key seconds`.
+pub fn expire<K: ToRedisArgs>(key: K, seconds: usize) -> Cmd {
+    let mut cmd = Cmd::new();
+    cmd.arg("EXPIRE").arg(key).arg(seconds);
+    cmd
+}
+
+/// Fluent builder for the extra flags `SET` accepts alongside its key and
+/// value, passed to `set_options`.
+#[derive(Clone, Copy, Default)]
+pub struct SetOptions {
+    expiry: Option<Expiry>,
+    nx: bool,
+    xx: bool,
+    keepttl: bool,
+}
+
+impl SetOptions {
+    pub fn new() -> SetOptions {
+        SetOptions {
+            expiry: None,
+            nx: false,
+            xx: false,
+            keepttl: false,
+        }
+    }
+
+    pub fn expiry(mut self, expiry: Expiry) -> SetOptions {
+        self.expiry = Some(expiry);
+        self
+    }
+
+    pub fn nx(mut self) -> SetOptions {
+        self.nx = true;
+        self
+    }
+
+    pub fn xx(mut self) -> SetOptions {
+        self.xx = true;
+        self
+    }
+
+    pub fn keepttl(mut self) -> SetOptions {
+        self.keepttl = true;
+        self
+    }
+}
+
+/// Builds `SET key value [NX|XX] [EX seconds|PX ms|EXAT ts|PXAT ts|PERSIST] [KEEPTTL]`.
+pub fn set_options<K: ToRedisArgs, V: ToRedisArgs>(key: K, value: V, options: SetOptions) -> Cmd {
+    let mut cmd = Cmd::new();
+    cmd.arg("SET").arg(key).arg(value);
+    if options.nx {
+        cmd.arg("NX");
+    }
+    if options.xx {
+        cmd.arg("XX");
+    }
+    if let Some(expiry) = options.expiry {
+        cmd.arg(expiry);
+    }
+    if options.keepttl {
+        cmd.arg("KEEPTTL");
+    }
+    cmd
+}
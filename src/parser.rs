@@ -1,15 +1,18 @@
 use std::io::{Read, Cursor};
+use std::mem;
 
 use types::{Value, ErrorKind, RedisResult};
 
 pub struct Parser<T> {
     reader: T,
+    incremental: IncrementalState,
 }
 
 impl<T: Read> Parser<T> {
     pub fn new(reader: T) -> Parser<T> {
         Parser {
-            reader: reader
+            reader: reader,
+            incremental: IncrementalState::new(),
         }
     }
 
@@ -21,10 +24,111 @@ impl<T: Read> Parser<T> {
             ':' => self.parse_int_value(),
             '$' => self.parse_data_value(),
             '*' => self.parse_bulk_value(),
+            '_' => self.parse_null_value(),
+            '#' => self.parse_boolean_value(),
+            ',' => self.parse_double_value(),
+            '(' => self.parse_bignumber_value(),
+            '=' => self.parse_verbatim_value(),
+            '%' => self.parse_map_value(),
+            '~' => self.parse_set_value(),
+            '>' => self.parse_push_value(),
             _ => Err((ErrorKind::ResponseError, "Invalid response when parsing value").into())
         }
     }
 
+    fn parse_null_value(&mut self) -> RedisResult<Value> {
+        try!(self.read_line());
+        Ok(Value::Nil)
+    }
+
+    fn parse_boolean_value(&mut self) -> RedisResult<Value> {
+        let line = try!(self.read_string_line());
+        match line.trim() {
+            "t" => Ok(Value::Boolean(true)),
+            "f" => Ok(Value::Boolean(false)),
+            _ => Err((ErrorKind::ResponseError, "Invalid boolean value").into())
+        }
+    }
+
+    fn parse_double_value(&mut self) -> RedisResult<Value> {
+        let line = try!(self.read_string_line());
+        match line.trim() {
+            "inf" => Ok(Value::Double(::std::f64::INFINITY)),
+            "-inf" => Ok(Value::Double(::std::f64::NEG_INFINITY)),
+            s => match s.parse::<f64>() {
+                Ok(v) => Ok(Value::Double(v)),
+                Err(_) => Err((ErrorKind::ResponseError, "Expected double, got garbage").into())
+            }
+        }
+    }
+
+    fn parse_bignumber_value(&mut self) -> RedisResult<Value> {
+        // big numbers don't fit in any of our numeric types, so we hand
+        // them back as the raw decimal string, same as a status reply.
+        let line = try!(self.read_string_line());
+        Ok(Value::Status(line))
+    }
+
+    fn parse_verbatim_value(&mut self) -> RedisResult<Value> {
+        let length = try!(self.read_int_value());
+        if length < 0 {
+            Ok(Value::Nil)
+        } else {
+            let raw = try!(self.read(length as usize));
+            try!(self.expect_char('\r'));
+            try!(self.expect_char('\n'));
+            if raw.len() < 4 || raw[3] != b':' {
+                return Err((ErrorKind::ResponseError, "Invalid verbatim string").into());
+            }
+            let format = try!(String::from_utf8(raw[..3].to_vec()));
+            let payload = raw[4..].to_vec();
+            Ok(Value::VerbatimString(format, payload))
+        }
+    }
+
+    fn parse_map_value(&mut self) -> RedisResult<Value> {
+        let length = try!(self.read_int_value());
+        if length < 0 {
+            Ok(Value::Nil)
+        } else {
+            let mut rv = Vec::with_capacity(length as usize);
+            for _ in 0..length {
+                let key = try!(self.parse_value());
+                let value = try!(self.parse_value());
+                rv.push((key, value));
+            }
+            Ok(Value::Map(rv))
+        }
+    }
+
+    fn parse_set_value(&mut self) -> RedisResult<Value> {
+        let length = try!(self.read_int_value());
+        if length < 0 {
+            Ok(Value::Nil)
+        } else {
+            let mut rv = vec![];
+            rv.reserve(length as usize);
+            for _ in 0..length {
+                rv.push(try!(self.parse_value()));
+            }
+            Ok(Value::Set(rv))
+        }
+    }
+
+    fn parse_push_value(&mut self) -> RedisResult<Value> {
+        let length = try!(self.read_int_value());
+        if length < 0 {
+            Ok(Value::Nil)
+        } else {
+            let mut rv = vec![];
+            rv.reserve(length as usize);
+            for _ in 0..length {
+                rv.push(try!(self.parse_value()));
+            }
+            Ok(Value::Push(rv))
+        }
+    }
+
     fn parse_int_value(&mut self) -> RedisResult<Value> {
         Ok(Value::Int(try!(self.read_int_value())))
     }
@@ -162,6 +266,83 @@ impl<T: Read> Parser<T> {
             Err((ErrorKind::ResponseError, "Invalid byte in Response").into())
         }
     }
+
+    /// Feeds a chunk of freshly-read bytes to the parser. Unlike
+    /// `parse_value`, this never blocks and never treats a short read as an
+    /// error: if `buf` runs out before a full value is available, progress
+    /// is saved internally (the nesting of any in-progress bulk arrays, the
+    /// bytes already collected for an in-progress bulk string, and its
+    /// expected length) and `ParseStep::NeedMore` is returned so the caller
+    /// can feed the next chunk and resume exactly where parsing left off.
+    ///
+    /// On `ParseStep::Complete(value, consumed)`, `consumed` is how many
+    /// bytes of *this* `buf` were used to produce `value`; any trailing
+    /// bytes belong to the next reply and should be passed (from that
+    /// offset) to the next call.
+    pub fn parse_value_incremental(&mut self, buf: &[u8]) -> RedisResult<ParseStep> {
+        let mut i = 0usize;
+        loop {
+            match try!(self.incremental_step(buf, &mut i)) {
+                Some(value) => {
+                    match fold(&mut self.incremental.stack, value) {
+                        Some(top) => return Ok(ParseStep::Complete(top, i)),
+                        None => continue,
+                    }
+                }
+                None => return Ok(ParseStep::NeedMore),
+            }
+        }
+    }
+
+    fn incremental_step(&mut self, buf: &[u8], i: &mut usize) -> RedisResult<Option<Value>> {
+        loop {
+            match self.incremental.leaf {
+                Leaf::AwaitType => {
+                    if *i >= buf.len() {
+                        return Ok(None);
+                    }
+                    let byte = buf[*i];
+                    *i += 1;
+                    self.incremental.leaf = try!(start_leaf(byte));
+                }
+                Leaf::Line(..) => {
+                    if !fill_line(&mut self.incremental.leaf, buf, i) {
+                        return Ok(None);
+                    }
+                    let (goal, line) = match mem::replace(&mut self.incremental.leaf, Leaf::AwaitType) {
+                        Leaf::Line(goal, line) => (goal, line),
+                        _ => unreachable!(),
+                    };
+                    match try!(finish_line(goal, line)) {
+                        LineOutcome::Value(v) => return Ok(Some(v)),
+                        LineOutcome::Payload(container, len) => {
+                            self.incremental.leaf = Leaf::Payload(container, len, Vec::with_capacity(len), 0);
+                        }
+                        LineOutcome::Items(container, 0) => {
+                            return Ok(Some(build_container_value(container, vec![])));
+                        }
+                        LineOutcome::Items(container, count) => {
+                            self.incremental.stack.push(Frame {
+                                container: container,
+                                remaining: count,
+                                items: Vec::with_capacity(count),
+                            });
+                        }
+                    }
+                }
+                Leaf::Payload(..) => {
+                    if !try!(fill_payload(&mut self.incremental.leaf, buf, i)) {
+                        return Ok(None);
+                    }
+                    let (container, raw) = match mem::replace(&mut self.incremental.leaf, Leaf::AwaitType) {
+                        Leaf::Payload(container, _, raw, _) => (container, raw),
+                        _ => unreachable!(),
+                    };
+                    return Ok(Some(try!(finish_payload(container, raw))));
+                }
+            }
+        }
+    }
 }
 
 pub fn parse_redis_value(bytes: &[u8]) -> RedisResult<Value> {
@@ -169,6 +350,272 @@ pub fn parse_redis_value(bytes: &[u8]) -> RedisResult<Value> {
     parser.parse_value()
 }
 
+/// The result of feeding a chunk of bytes to `Parser::parse_value_incremental`.
+pub enum ParseStep {
+    /// A full value was parsed; the `usize` is how many bytes of the chunk
+    /// passed to this call were consumed producing it.
+    Complete(Value, usize),
+    /// The chunk ran out before the value was complete; internal state has
+    /// been saved so parsing resumes on the next call.
+    NeedMore,
+}
+
+#[derive(Clone, Debug)]
+enum Container {
+    Data,
+    Bulk,
+    Map,
+    Set,
+    Push,
+    Verbatim,
+}
+
+#[derive(Clone, Debug)]
+enum LineGoal {
+    Status,
+    Error,
+    Int,
+    Boolean,
+    Double,
+    BigNumber,
+    Null,
+    Length(Container),
+}
+
+enum Leaf {
+    /// waiting for the one-byte type prefix of the next value
+    AwaitType,
+    /// accumulating a CRLF-terminated line for the given goal
+    Line(LineGoal, Vec<u8>),
+    /// accumulating the raw bytes of a bulk/verbatim payload; the trailing
+    /// `u8` counts how many of the terminating `\r\n` bytes have been seen
+    Payload(Container, usize, Vec<u8>, u8),
+}
+
+struct Frame {
+    container: Container,
+    remaining: usize,
+    items: Vec<Value>,
+}
+
+/// State carried across calls to `parse_value_incremental` so that a reply
+/// spanning several chunks resumes instead of being reparsed from scratch.
+struct IncrementalState {
+    leaf: Leaf,
+    stack: Vec<Frame>,
+}
+
+impl IncrementalState {
+    fn new() -> IncrementalState {
+        IncrementalState {
+            leaf: Leaf::AwaitType,
+            stack: vec![],
+        }
+    }
+}
+
+enum LineOutcome {
+    Value(Value),
+    Payload(Container, usize),
+    Items(Container, usize),
+}
+
+fn start_leaf(byte: u8) -> RedisResult<Leaf> {
+    let goal = match byte as char {
+        '+' => LineGoal::Status,
+        '-' => LineGoal::Error,
+        ':' => LineGoal::Int,
+        '#' => LineGoal::Boolean,
+        ',' => LineGoal::Double,
+        '(' => LineGoal::BigNumber,
+        '_' => LineGoal::Null,
+        '$' => LineGoal::Length(Container::Data),
+        '*' => LineGoal::Length(Container::Bulk),
+        '%' => LineGoal::Length(Container::Map),
+        '~' => LineGoal::Length(Container::Set),
+        '>' => LineGoal::Length(Container::Push),
+        '=' => LineGoal::Length(Container::Verbatim),
+        _ => return Err((ErrorKind::ResponseError, "Invalid response when parsing value").into()),
+    };
+    Ok(Leaf::Line(goal, vec![]))
+}
+
+fn fill_line(leaf: &mut Leaf, buf: &[u8], i: &mut usize) -> bool {
+    let line = match *leaf {
+        Leaf::Line(_, ref mut line) => line,
+        _ => unreachable!(),
+    };
+    while *i < buf.len() {
+        let b = buf[*i];
+        *i += 1;
+        if b == b'\n' {
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+            return true;
+        }
+        line.push(b);
+    }
+    false
+}
+
+fn fill_payload(leaf: &mut Leaf, buf: &[u8], i: &mut usize) -> RedisResult<bool> {
+    let (len, body, crlf) = match *leaf {
+        Leaf::Payload(_, len, ref mut body, ref mut crlf) => (len, body, crlf),
+        _ => unreachable!(),
+    };
+    if body.len() < len {
+        let need = len - body.len();
+        let available = buf.len() - *i;
+        let take = if need < available { need } else { available };
+        body.extend_from_slice(&buf[*i..*i + take]);
+        *i += take;
+        if body.len() < len {
+            return Ok(false);
+        }
+    }
+    while *crlf < 2 {
+        if *i >= buf.len() {
+            return Ok(false);
+        }
+        let expected = if *crlf == 0 { b'\r' } else { b'\n' };
+        if buf[*i] != expected {
+            return Err((ErrorKind::ResponseError, "Invalid byte in Response").into());
+        }
+        *i += 1;
+        *crlf += 1;
+    }
+    Ok(true)
+}
+
+fn finish_line(goal: LineGoal, line: Vec<u8>) -> RedisResult<LineOutcome> {
+    match goal {
+        LineGoal::Null => Ok(LineOutcome::Value(Value::Nil)),
+        LineGoal::Status => {
+            let s = try!(String::from_utf8(line));
+            if s == "OK" {
+                Ok(LineOutcome::Value(Value::Okay))
+            } else {
+                Ok(LineOutcome::Value(Value::Status(s)))
+            }
+        }
+        LineGoal::Error => {
+            let s = try!(String::from_utf8(line));
+            let desc = "An error was signaled by the server";
+            let mut pieces = s.splitn(2, ' ');
+            let kind = match pieces.next().unwrap() {
+                "ERR" => ErrorKind::ResponseError,
+                "EXECABORT" => ErrorKind::ExecAbortError,
+                "LOADING" => ErrorKind::BusyLoadingError,
+                "NOSCRIPT" => ErrorKind::NoScriptError,
+                code => ErrorKind::ExtensionError(code.to_string()),
+            };
+            match pieces.next() {
+                Some(detail) => Err((kind, desc, detail.to_string()).into()),
+                None => Err((kind, desc).into()),
+            }
+        }
+        LineGoal::Int => {
+            let s = try!(String::from_utf8(line));
+            match s.trim().parse::<i64>() {
+                Ok(v) => Ok(LineOutcome::Value(Value::Int(v))),
+                Err(_) => Err((ErrorKind::ResponseError, "Expected integer, got garbage").into()),
+            }
+        }
+        LineGoal::Boolean => {
+            let s = try!(String::from_utf8(line));
+            match s.trim() {
+                "t" => Ok(LineOutcome::Value(Value::Boolean(true))),
+                "f" => Ok(LineOutcome::Value(Value::Boolean(false))),
+                _ => Err((ErrorKind::ResponseError, "Invalid boolean value").into()),
+            }
+        }
+        LineGoal::Double => {
+            let s = try!(String::from_utf8(line));
+            match s.trim() {
+                "inf" => Ok(LineOutcome::Value(Value::Double(::std::f64::INFINITY))),
+                "-inf" => Ok(LineOutcome::Value(Value::Double(::std::f64::NEG_INFINITY))),
+                rest => match rest.parse::<f64>() {
+                    Ok(v) => Ok(LineOutcome::Value(Value::Double(v))),
+                    Err(_) => Err((ErrorKind::ResponseError, "Expected double, got garbage").into()),
+                }
+            }
+        }
+        LineGoal::BigNumber => {
+            let s = try!(String::from_utf8(line));
+            Ok(LineOutcome::Value(Value::Status(s)))
+        }
+        LineGoal::Length(container) => {
+            let s = try!(String::from_utf8(line));
+            let len = match s.trim().parse::<i64>() {
+                Ok(v) => v,
+                Err(_) => return Err((ErrorKind::ResponseError, "Expected integer, got garbage").into()),
+            };
+            if len < 0 {
+                return Ok(LineOutcome::Value(Value::Nil));
+            }
+            match container {
+                Container::Data | Container::Verbatim => Ok(LineOutcome::Payload(container, len as usize)),
+                Container::Map => Ok(LineOutcome::Items(container, (len as usize) * 2)),
+                Container::Bulk | Container::Set | Container::Push => Ok(LineOutcome::Items(container, len as usize)),
+            }
+        }
+    }
+}
+
+fn finish_payload(container: Container, raw: Vec<u8>) -> RedisResult<Value> {
+    match container {
+        Container::Data => Ok(Value::Data(raw)),
+        Container::Verbatim => {
+            if raw.len() < 4 || raw[3] != b':' {
+                return Err((ErrorKind::ResponseError, "Invalid verbatim string").into());
+            }
+            let format = try!(String::from_utf8(raw[..3].to_vec()));
+            Ok(Value::VerbatimString(format, raw[4..].to_vec()))
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn build_container_value(container: Container, items: Vec<Value>) -> Value {
+    match container {
+        Container::Bulk => Value::Bulk(items),
+        Container::Set => Value::Set(items),
+        Container::Push => Value::Push(items),
+        Container::Map => {
+            let mut pairs = Vec::with_capacity(items.len() / 2);
+            let mut it = items.into_iter();
+            while let (Some(k), Some(v)) = (it.next(), it.next()) {
+                pairs.push((k, v));
+            }
+            Value::Map(pairs)
+        }
+        Container::Data | Container::Verbatim => unreachable!(),
+    }
+}
+
+/// Folds a just-completed value into the innermost open container frame,
+/// returning `Some` with the fully-assembled top-level value once the last
+/// frame (if any) closes, or `None` while frames are still awaiting more
+/// children.
+fn fold(stack: &mut Vec<Frame>, mut v: Value) -> Option<Value> {
+    loop {
+        let done = match stack.last_mut() {
+            None => return Some(v),
+            Some(frame) => {
+                frame.items.push(v);
+                frame.remaining -= 1;
+                frame.remaining == 0
+            }
+        };
+        if !done {
+            return None;
+        }
+        let frame = stack.pop().unwrap();
+        v = build_container_value(frame.container, frame.items);
+    }
+}
+
 #[cfg(test)]
 mod test {
     #[cfg(test)]
@@ -246,4 +693,129 @@ mod test {
                    parse_redis_value(bulk2).unwrap());
         assert_eq!(Value::Nil, parse_redis_value(bulk3).unwrap());
     }
+
+    #[test]
+    fn test_parse_resp3_null() {
+        let bytes = "_\r\n".as_bytes();
+        assert_eq!(Value::Nil, parse_redis_value(bytes).unwrap());
+    }
+
+    #[test]
+    fn test_parse_resp3_boolean() {
+        let bytes_t = "#t\r\n".as_bytes();
+        let bytes_f = "#f\r\n".as_bytes();
+        assert_eq!(Value::Boolean(true), parse_redis_value(bytes_t).unwrap());
+        assert_eq!(Value::Boolean(false), parse_redis_value(bytes_f).unwrap());
+    }
+
+    #[test]
+    fn test_parse_resp3_double() {
+        let bytes = ",3.14\r\n".as_bytes();
+        assert_eq!(Value::Double(3.14f64), parse_redis_value(bytes).unwrap());
+    }
+
+    #[test]
+    fn test_parse_resp3_bignumber() {
+        let bytes = "(3492890328409238509324850943850943825024385\r\n".as_bytes();
+        assert_eq!(Value::Status("3492890328409238509324850943850943825024385".to_string()),
+                   parse_redis_value(bytes).unwrap());
+    }
+
+    #[test]
+    fn test_parse_resp3_verbatim_string() {
+        let bytes = "=15\r\ntxt:Some string\r\n".as_bytes();
+        assert_eq!(Value::VerbatimString("txt".to_string(), "Some string".as_bytes().to_vec()),
+                   parse_redis_value(bytes).unwrap());
+    }
+
+    #[test]
+    fn test_parse_resp3_map() {
+        let bytes = "%2\r\n$3\r\nfoo\r\n:1\r\n$3\r\nbar\r\n:2\r\n".as_bytes();
+        assert_eq!(Value::Map(vec![
+                       (Value::Data("foo".as_bytes().to_vec()), Value::Int(1)),
+                       (Value::Data("bar".as_bytes().to_vec()), Value::Int(2)),
+                   ]),
+                   parse_redis_value(bytes).unwrap());
+    }
+
+    #[test]
+    fn test_parse_resp3_set() {
+        let bytes = "~2\r\n$3\r\nfoo\r\n$3\r\nbar\r\n".as_bytes();
+        assert_eq!(Value::Set(vec![Value::Data("foo".as_bytes().to_vec()),
+                                    Value::Data("bar".as_bytes().to_vec())]),
+                   parse_redis_value(bytes).unwrap());
+    }
+
+    #[test]
+    fn test_parse_resp3_push() {
+        let bytes = ">2\r\n$7\r\nmessage\r\n$5\r\nhello\r\n".as_bytes();
+        assert_eq!(Value::Push(vec![Value::Data("message".as_bytes().to_vec()),
+                                     Value::Data("hello".as_bytes().to_vec())]),
+                   parse_redis_value(bytes).unwrap());
+    }
+
+    fn feed_one_byte_at_a_time(bytes: &[u8]) -> Value {
+        let mut parser = Parser::new(Cursor::new(&[][..]));
+        for (idx, &b) in bytes.iter().enumerate() {
+            match parser.parse_value_incremental(&bytes[idx..idx + 1]).unwrap() {
+                ParseStep::Complete(v, consumed) => {
+                    assert_eq!(consumed, 1);
+                    return v;
+                }
+                ParseStep::NeedMore => continue,
+            }
+        }
+        panic!("ran out of input before the value completed");
+    }
+
+    #[test]
+    fn test_incremental_byte_at_a_time() {
+        let bulk = "*2\r\n:1\r\n$6\r\nfoobar\r\n".as_bytes();
+        assert_eq!(Value::Bulk(vec![Value::Int(1),
+                                     Value::Data("foobar".as_bytes().to_vec())]),
+                   feed_one_byte_at_a_time(bulk));
+    }
+
+    #[test]
+    fn test_incremental_splits_multibyte_utf8() {
+        // "café".as_bytes() is `caf` followed by the 2-byte UTF-8 sequence
+        // for "é"; split the chunk right inside that sequence to make sure
+        // the bulk string byte buffer (not a decoded string) is what gets
+        // resumed across calls.
+        let reply = "$5\r\ncaf\u{e9}\r\n".as_bytes();
+        let mut parser = Parser::new(Cursor::new(&[][..]));
+
+        let split = reply.len() - 3;
+        match parser.parse_value_incremental(&reply[..split]).unwrap() {
+            ParseStep::NeedMore => {}
+            ParseStep::Complete(..) => panic!("should not have completed yet"),
+        }
+        match parser.parse_value_incremental(&reply[split..]).unwrap() {
+            ParseStep::Complete(v, consumed) => {
+                assert_eq!(v, Value::Data("caf\u{e9}".as_bytes().to_vec()));
+                assert_eq!(consumed, reply.len() - split);
+            }
+            ParseStep::NeedMore => panic!("should have completed"),
+        }
+    }
+
+    #[test]
+    fn test_incremental_consumes_only_one_reply_per_chunk() {
+        let mut parser = Parser::new(Cursor::new(&[][..]));
+        let chunk = "+OK\r\n+ALSO-OK\r\n".as_bytes();
+        match parser.parse_value_incremental(chunk).unwrap() {
+            ParseStep::Complete(v, consumed) => {
+                assert_eq!(v, Value::Okay);
+                assert_eq!(consumed, 5);
+                match parser.parse_value_incremental(&chunk[consumed..]).unwrap() {
+                    ParseStep::Complete(v2, consumed2) => {
+                        assert_eq!(v2, Value::Status("ALSO-OK".to_string()));
+                        assert_eq!(consumed2, chunk.len() - consumed);
+                    }
+                    ParseStep::NeedMore => panic!("should have completed"),
+                }
+            }
+            ParseStep::NeedMore => panic!("should have completed"),
+        }
+    }
 }
@@ -1,6 +1,9 @@
 use std::io;
 use std::error::Error;
+use std::str::from_utf8;
 use std::string::FromUtf8Error;
+use std::hash::Hash;
+use std::collections::{HashMap, BTreeMap, HashSet, BTreeSet};
 
 /// Error kinds
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -24,7 +27,7 @@ pub enum ErrorKind {
 }
 
 /// Redis Value Enum
-#[derive(PartialEq, Eq, Clone, Debug)]
+#[derive(PartialEq, Clone, Debug)]
 pub enum Value {
     /// nil response
     Nil,
@@ -37,7 +40,20 @@ pub enum Value {
     /// a status response, normally a string
     Status(String),
     /// "OK" response
-    Okay,    
+    Okay,
+    /// RESP3 double response
+    Double(f64),
+    /// RESP3 boolean response
+    Boolean(bool),
+    /// RESP3 map response, a list of key/value pairs
+    Map(Vec<(Value, Value)>),
+    /// RESP3 set response
+    Set(Vec<Value>),
+    /// RESP3 out-of-band push message
+    Push(Vec<Value>),
+    /// RESP3 verbatim string response, carrying its format (e.g. `txt`/`mkd`)
+    /// alongside the payload
+    VerbatimString(String, Vec<u8>),
 }
 
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -278,3 +294,266 @@ to_redis_args_for_array! (
    30  31   32
 );
 
+impl<K: ToRedisArgs + Eq + Hash, V: ToRedisArgs> ToRedisArgs for HashMap<K, V> {
+    fn to_redis_args(&self) -> Vec<Vec<u8>> {
+        let mut rv = vec![];
+        for (key, value) in self.iter() {
+            rv.extend(key.to_redis_args().into_iter());
+            rv.extend(value.to_redis_args().into_iter());
+        }
+        rv
+    }
+
+    fn is_single_arg(&self) -> bool {
+        false
+    }
+}
+
+impl<K: ToRedisArgs + Ord, V: ToRedisArgs> ToRedisArgs for BTreeMap<K, V> {
+    fn to_redis_args(&self) -> Vec<Vec<u8>> {
+        let mut rv = vec![];
+        for (key, value) in self.iter() {
+            rv.extend(key.to_redis_args().into_iter());
+            rv.extend(value.to_redis_args().into_iter());
+        }
+        rv
+    }
+
+    fn is_single_arg(&self) -> bool {
+        false
+    }
+}
+
+impl<T: ToRedisArgs + Eq + Hash> ToRedisArgs for HashSet<T> {
+    fn to_redis_args(&self) -> Vec<Vec<u8>> {
+        let mut rv = vec![];
+        for item in self.iter() {
+            rv.extend(item.to_redis_args().into_iter());
+        }
+        rv
+    }
+
+    fn is_single_arg(&self) -> bool {
+        false
+    }
+}
+
+impl<T: ToRedisArgs + Ord> ToRedisArgs for BTreeSet<T> {
+    fn to_redis_args(&self) -> Vec<Vec<u8>> {
+        let mut rv = vec![];
+        for item in self.iter() {
+            rv.extend(item.to_redis_args().into_iter());
+        }
+        rv
+    }
+
+    fn is_single_arg(&self) -> bool {
+        false
+    }
+}
+
+/// Expiry options shared by commands like `SET` and `GETEX`.
+#[derive(Clone, Copy, Debug)]
+pub enum Expiry {
+    /// Set the expiry in seconds
+    EX(usize),
+    /// Set the expiry in milliseconds
+    PX(usize),
+    /// Set the expiry as a unix timestamp in seconds
+    EXAT(usize),
+    /// Set the expiry as a unix timestamp in milliseconds
+    PXAT(usize),
+    /// Remove any existing expiry from the key
+    PERSIST,
+}
+
+impl ToRedisArgs for Expiry {
+    fn to_redis_args(&self) -> Vec<Vec<u8>> {
+        match *self {
+            Expiry::EX(secs) => vec![b"EX".to_vec(), secs.to_string().into_bytes()],
+            Expiry::PX(ms) => vec![b"PX".to_vec(), ms.to_string().into_bytes()],
+            Expiry::EXAT(ts) => vec![b"EXAT".to_vec(), ts.to_string().into_bytes()],
+            Expiry::PXAT(ts) => vec![b"PXAT".to_vec(), ts.to_string().into_bytes()],
+            Expiry::PERSIST => vec![b"PERSIST".to_vec()],
+        }
+    }
+
+    fn is_single_arg(&self) -> bool {
+        false
+    }
+}
+
+/// This trait is used to convert a `Value` coming back from the server into
+/// a concrete Rust type. It is the counterpart to `ToRedisArgs`.
+pub trait FromRedisValue: Sized {
+    fn from_redis_value(v: &Value) -> RedisResult<Self>;
+
+    /// Specialization hook: lets a single element type (namely `u8`) opt in
+    /// to being built directly from a `Value::Data` byte buffer so that
+    /// `Vec<u8>` can be special-cased without conflicting with the generic
+    /// `Vec<T>` impl below.
+    fn from_byte_vec(_vec: &[u8]) -> Option<Vec<Self>> {
+        None
+    }
+}
+
+/// Convenience free function mirroring `FromRedisValue::from_redis_value`.
+pub fn from_redis_value<T: FromRedisValue>(v: &Value) -> RedisResult<T> {
+    T::from_redis_value(v)
+}
+
+fn type_error<T>(desc: &'static str, v: &Value) -> RedisResult<T> {
+    Err((ErrorKind::TypeError, desc, format!("(response was {:?})", v)).into())
+}
+
+macro_rules! from_redis_value_for_num {
+    ($t:ty) => {
+        impl FromRedisValue for $t {
+            fn from_redis_value(v: &Value) -> RedisResult<$t> {
+                match *v {
+                    Value::Int(i) => Ok(i as $t),
+                    Value::Status(ref s) => match s.trim().parse::<$t>() {
+                        Ok(rv) => Ok(rv),
+                        Err(_) => type_error("Could not convert from string", v),
+                    },
+                    Value::Data(ref bytes) => match from_utf8(bytes) {
+                        Ok(s) => match s.trim().parse::<$t>() {
+                            Ok(rv) => Ok(rv),
+                            Err(_) => type_error("Could not convert from string", v),
+                        },
+                        Err(_) => type_error("Invalid UTF-8 string", v),
+                    },
+                    _ => type_error("Response type not convertible to numeric type", v),
+                }
+            }
+        }
+    }
+}
+
+from_redis_value_for_num!(i8);
+from_redis_value_for_num!(i16);
+from_redis_value_for_num!(u16);
+from_redis_value_for_num!(i32);
+from_redis_value_for_num!(u32);
+from_redis_value_for_num!(i64);
+from_redis_value_for_num!(u64);
+from_redis_value_for_num!(f32);
+from_redis_value_for_num!(f64);
+from_redis_value_for_num!(isize);
+from_redis_value_for_num!(usize);
+
+impl FromRedisValue for u8 {
+    fn from_redis_value(v: &Value) -> RedisResult<u8> {
+        match *v {
+            Value::Int(i) => Ok(i as u8),
+            Value::Status(ref s) => match s.trim().parse::<u8>() {
+                Ok(rv) => Ok(rv),
+                Err(_) => type_error("Could not convert from string", v),
+            },
+            Value::Data(ref bytes) => match from_utf8(bytes) {
+                Ok(s) => match s.trim().parse::<u8>() {
+                    Ok(rv) => Ok(rv),
+                    Err(_) => type_error("Could not convert from string", v),
+                },
+                Err(_) => type_error("Invalid UTF-8 string", v),
+            },
+            _ => type_error("Response type not convertible to numeric type", v),
+        }
+    }
+
+    fn from_byte_vec(vec: &[u8]) -> Option<Vec<u8>> {
+        Some(vec.to_vec())
+    }
+}
+
+impl FromRedisValue for bool {
+    fn from_redis_value(v: &Value) -> RedisResult<bool> {
+        match *v {
+            Value::Int(i) => Ok(i != 0),
+            Value::Okay => Ok(true),
+            Value::Status(ref s) => match s.as_str() {
+                "1" => Ok(true),
+                "0" => Ok(false),
+                _ => type_error("Response status not boolean compatible", v),
+            },
+            _ => type_error("Response type not boolean compatible", v),
+        }
+    }
+}
+
+impl FromRedisValue for String {
+    fn from_redis_value(v: &Value) -> RedisResult<String> {
+        match *v {
+            Value::Data(ref bytes) => Ok(try!(String::from_utf8(bytes.clone()))),
+            Value::Status(ref s) => Ok(s.clone()),
+            Value::Okay => Ok("OK".to_string()),
+            _ => type_error("Response type not string compatible", v),
+        }
+    }
+}
+
+impl<T: FromRedisValue> FromRedisValue for Option<T> {
+    fn from_redis_value(v: &Value) -> RedisResult<Option<T>> {
+        match *v {
+            Value::Nil => Ok(None),
+            _ => Ok(Some(try!(from_redis_value(v)))),
+        }
+    }
+}
+
+impl<T: FromRedisValue> FromRedisValue for Vec<T> {
+    fn from_redis_value(v: &Value) -> RedisResult<Vec<T>> {
+        match *v {
+            Value::Bulk(ref items) => items.iter().map(|item| from_redis_value(item)).collect(),
+            Value::Data(ref bytes) => match FromRedisValue::from_byte_vec(bytes) {
+                Some(rv) => Ok(rv),
+                None => type_error("Response type not vector compatible", v),
+            },
+            Value::Nil => Ok(vec![]),
+            _ => type_error("Response type not vector compatible", v),
+        }
+    }
+}
+
+impl FromRedisValue for () {
+    fn from_redis_value(v: &Value) -> RedisResult<()> {
+        match *v {
+            Value::Okay | Value::Status(_) => Ok(()),
+            _ => type_error("Response type not () compatible", v),
+        }
+    }
+}
+
+macro_rules! from_redis_value_for_tuple {
+    () => ();
+    ($($name: ident,)+) => {
+        impl<$($name: FromRedisValue),*> FromRedisValue for ($($name,)*) {
+            #[allow(non_snake_case, unused_variables, unused_mut)]
+            fn from_redis_value(v: &Value) -> RedisResult<($($name,)*)> {
+                match *v {
+                    Value::Bulk(ref items) => {
+                        let mut n = 0;
+                        $(let $name = (); n += 1;)*
+                        if items.len() != n {
+                            return type_error("Bulk response has wrong length for tuple", v);
+                        }
+                        let mut iter = items.iter();
+                        $(let $name = try!(from_redis_value(iter.next().unwrap()));)*
+                        Ok(($($name,)*))
+                    }
+                    _ => type_error("Response type not tuple compatible", v),
+                }
+            }
+        }
+        from_redis_value_for_tuple_peel!($($name,)*);
+    }
+}
+
+macro_rules! from_redis_value_for_tuple_peel {
+    ($name: ident, $($other: ident,)*) => {
+        from_redis_value_for_tuple!($($other,)*);
+    }
+}
+
+from_redis_value_for_tuple! (T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, );
+